@@ -4,7 +4,7 @@
 use MORK::NDMORK::list::*;
 #[allow(unused_imports)]
 use MORK::RK::list::*;
-use MORK::Solver;
+use MORK::integration::Integration;
 
 fn main() {
 
@@ -14,19 +14,16 @@ fn main() {
 	let y_initial = vec![vec![-1.,0.5],vec![1.]];
 
 	// Choice of method
-	let mut method = MORK4b();
+	let method = MORK4b();
 
 	// Number of iterations and constant step size
 	let iterations = 100;
 	let h = 0.01;
 
-	// Initialize approximations
-	let mut y = y_initial.clone();
-	
-	// Aplies the method
-	for _ in 0..iterations {
-		y = method.approximate(t0, h, &f, &y);
-	}
+	// Drive the integration lazily, taking exactly `iterations` steps
+	let integration = Integration::new(method, t0, y_initial.clone(), h, &f, |_, _| false);
+
+	let y = integration.take(iterations).last().map(|(_, y)| y).unwrap_or(y_initial);
 
 	let solution = |t: f64| vec![vec![-0.5 * ((-t).exp() + t.cos()), 0.5 * ((-t).exp() - t.sin())],vec![t.cos()]];
 
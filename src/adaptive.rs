@@ -0,0 +1,174 @@
+//! Adaptive step-size control built on top of [Solver], using an embedded
+//! lower-order companion to estimate the local error of each step.
+
+use crate::state::State;
+use crate::Solver;
+use num_traits::Float;
+
+/// Tuning parameters for [approximate_adaptive]. The defaults follow the
+/// usual Dormand-Prince / Fehlberg conventions.
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptiveOptions<T: Float = f64> {
+    /// Absolute tolerance used in the mixed error norm.
+    pub atol: T,
+    /// Relative tolerance used in the mixed error norm.
+    pub rtol: T,
+    /// Safety factor applied to the proposed next step size.
+    pub safety: T,
+    /// Minimum allowed ratio between `h_new` and `h`.
+    pub facmin: T,
+    /// Maximum allowed ratio between `h_new` and `h`.
+    pub facmax: T,
+}
+
+impl<T: Float> Default for AdaptiveOptions<T> {
+    fn default() -> Self {
+        AdaptiveOptions {
+            atol: T::from(1e-6).unwrap(),
+            rtol: T::from(1e-3).unwrap(),
+            safety: T::from(0.9).unwrap(),
+            facmin: T::from(0.2).unwrap(),
+            facmax: T::from(5.0).unwrap(),
+        }
+    }
+}
+
+/// A [Solver] that additionally exposes an embedded lower-order companion,
+/// i.e. two approximations of orders `p` and `p - 1` computed from the same
+/// stages (as in Dormand-Prince 5(4) or Fehlberg 4(5)). This is the
+/// ingredient [approximate_adaptive] needs to estimate the local error of a
+/// step without doubling the work.
+pub trait EmbeddedSolver<T: Float = f64, St: State<T> = Vec<Vec<T>>>: Solver<T, St> {
+    /// Order `p` of the higher-order approximation returned alongside the
+    /// lower-order one.
+    fn order(&self) -> u32;
+
+    /// Given a differential equation function, initial instant, initial
+    /// values, and a step size, returns `(y_high, y_low)`, the higher- and
+    /// lower-order approximations produced from the same stages.
+    fn approximate_embedded(
+        &mut self,
+        t0: T,
+        h: T,
+        f: &dyn Fn(T, &St) -> Vec<T>,
+        y0: &St,
+    ) -> (St, St);
+}
+
+/// Advances one accepted step of `method`, automatically choosing `h`.
+///
+/// Starting from `(t0, y0)` and an initial guess `h`, repeatedly takes a
+/// step with [EmbeddedSolver::approximate_embedded], estimates the local
+/// error with [State::weighted_norm], and retries with a reduced step size
+/// until the error falls at or below `1`. Returns `(t_next, h_used,
+/// h_next, y)`, where `h_used` is the step size that produced the
+/// accepted state and `h_next` is the step size recommended for the
+/// following call.
+pub fn approximate_adaptive<T, St, M>(
+    method: &mut M,
+    t0: T,
+    h: T,
+    f: &dyn Fn(T, &St) -> Vec<T>,
+    y0: &St,
+    options: &AdaptiveOptions<T>,
+) -> (T, T, T, St)
+where
+    T: Float,
+    St: State<T>,
+    M: EmbeddedSolver<T, St>,
+{
+    let p = T::from(method.order()).unwrap();
+    let mut h = h;
+
+    loop {
+        let (y_high, y_low) = method.approximate_embedded(t0, h, f, y0);
+        let err = y_high
+            .weighted_norm(&y_low, options.atol, options.rtol)
+            .max(T::from(1e-300).unwrap());
+
+        let factor = options.safety * err.powf(-T::one() / p);
+        let h_next = h * factor.max(options.facmin).min(options.facmax);
+
+        if err <= T::one() {
+            return (t0 + h, h, h_next, y_high);
+        }
+
+        h = h_next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Forward Euler / explicit trapezoid (Heun) pair for y' = -y: the "low"
+    // order estimate is plain forward Euler, the "high" order estimate is the trapezoidal
+    // combination of the two endpoint slopes, giving a (2, 1) embedded
+    // pair to exercise against the closed-form solution y = y0 * e^(-t).
+    struct EulerTrapezoid;
+
+    impl Solver for EulerTrapezoid {
+        fn approximate(
+            &mut self,
+            t0: f64,
+            h: f64,
+            f: &dyn Fn(f64, &Vec<Vec<f64>>) -> Vec<f64>,
+            y0: &Vec<Vec<f64>>,
+        ) -> Vec<Vec<f64>> {
+            self.approximate_embedded(t0, h, f, y0).0
+        }
+    }
+
+    impl EmbeddedSolver for EulerTrapezoid {
+        fn order(&self) -> u32 {
+            2
+        }
+
+        fn approximate_embedded(
+            &mut self,
+            t0: f64,
+            h: f64,
+            f: &dyn Fn(f64, &Vec<Vec<f64>>) -> Vec<f64>,
+            y0: &Vec<Vec<f64>>,
+        ) -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
+            let k1 = f(t0, y0)[0];
+            let y_low = vec![vec![y0[0][0] + h * k1]];
+            let k2 = f(t0 + h, &y_low)[0];
+            let y_high = vec![vec![y0[0][0] + h * 0.5 * (k1 + k2)]];
+            (y_high, y_low)
+        }
+    }
+
+    #[test]
+    fn accepts_a_step_within_tolerance() {
+        let mut method = EulerTrapezoid;
+        let f = |_t: f64, y: &Vec<Vec<f64>>| vec![-y[0][0]];
+        let y0 = vec![vec![1.]];
+        let options = AdaptiveOptions::default();
+
+        let (t_next, h_used, h_next, y) = approximate_adaptive(&mut method, 0., 0.1, &f, &y0, &options);
+
+        assert!((t_next - h_used).abs() < 1e-12);
+        assert!(h_used > 0. && h_used <= 0.1);
+        assert!(h_next > 0.);
+        assert!(y[0][0] < y0[0][0] && y[0][0] > 0.);
+    }
+
+    #[test]
+    fn shrinks_the_step_until_the_error_tolerance_is_met() {
+        let mut method = EulerTrapezoid;
+        let f = |_t: f64, y: &Vec<Vec<f64>>| vec![-y[0][0]];
+        let y0 = vec![vec![1.]];
+        let options = AdaptiveOptions {
+            atol: 1e-10,
+            rtol: 1e-10,
+            ..AdaptiveOptions::default()
+        };
+
+        // Starting from a deliberately oversized step, the accepted h_used
+        // must have shrunk to satisfy the tight tolerance.
+        let (_, h_used, _, _) = approximate_adaptive(&mut method, 0., 10., &f, &y0, &options);
+
+        assert!(h_used < 10.);
+    }
+}
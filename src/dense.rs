@@ -0,0 +1,124 @@
+//! Continuous (dense) output between accepted steps: reconstructs
+//! `y(t0 + theta*h)` for `theta` in `[0, 1]` from a step's stage slopes,
+//! without forcing the caller down to tiny fixed steps.
+
+use crate::Solver;
+
+/// A [Solver] that, alongside the usual step to `t0 + h`, can build a
+/// continuous extension of that step: a polynomial in `theta` combining
+/// the stage slopes `k_j`, matching the scheme's order, that reconstructs
+/// `y(t0 + theta*h)` for any `theta` in `[0, 1]`.
+pub trait DenseOutputSolver: Solver {
+    /// Advances one step from `(t0, y0)`, returning the usual next state
+    /// together with a [DenseOutput] evaluator for that step.
+    fn approximate_dense(
+        &mut self,
+        t0: f64,
+        h: f64,
+        f: &dyn Fn(f64, &Vec<Vec<f64>>) -> Vec<f64>,
+        y0: &Vec<Vec<f64>>,
+    ) -> (Vec<Vec<f64>>, DenseOutput);
+}
+
+/// The continuous extension of a single accepted step `[t0, t0 + h]`,
+/// built by a [DenseOutputSolver] from that step's stage slopes.
+pub struct DenseOutput {
+    t0: f64,
+    h: f64,
+    evaluate: Box<dyn Fn(f64) -> Vec<Vec<f64>>>,
+}
+
+impl DenseOutput {
+    /// Builds a [DenseOutput] spanning `[t0, t0 + h]` from an evaluator
+    /// mapping `theta` in `[0, 1]` to `y(t0 + theta*h)`.
+    pub fn new(t0: f64, h: f64, evaluate: impl Fn(f64) -> Vec<Vec<f64>> + 'static) -> Self {
+        DenseOutput {
+            t0,
+            h,
+            evaluate: Box::new(evaluate),
+        }
+    }
+
+    /// The instant this step started at.
+    pub fn t0(&self) -> f64 {
+        self.t0
+    }
+
+    /// The step size used to produce this continuous extension.
+    pub fn h(&self) -> f64 {
+        self.h
+    }
+
+    /// `y(t0 + theta*h)` for `theta` in `[0, 1]`.
+    pub fn interpolate(&self, theta: f64) -> Vec<Vec<f64>> {
+        (self.evaluate)(theta)
+    }
+
+    /// `y(t)` for `t` in `[t0, t0 + h]`, converting `t` to `theta` internally.
+    pub fn evaluate_at(&self, t: f64) -> Vec<Vec<f64>> {
+        self.interpolate((t - self.t0) / self.h)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Explicit Euler, whose only stage slope doubles as a first-order
+    /// (linear) dense output: `y(t0 + theta*h) = y0 + theta*h*k`.
+    struct LinearEuler;
+
+    impl Solver for LinearEuler {
+        fn approximate(
+            &mut self,
+            t0: f64,
+            h: f64,
+            f: &dyn Fn(f64, &Vec<Vec<f64>>) -> Vec<f64>,
+            y0: &Vec<Vec<f64>>,
+        ) -> Vec<Vec<f64>> {
+            self.approximate_dense(t0, h, f, y0).0
+        }
+    }
+
+    impl DenseOutputSolver for LinearEuler {
+        fn approximate_dense(
+            &mut self,
+            t0: f64,
+            h: f64,
+            f: &dyn Fn(f64, &Vec<Vec<f64>>) -> Vec<f64>,
+            y0: &Vec<Vec<f64>>,
+        ) -> (Vec<Vec<f64>>, DenseOutput) {
+            let k = f(t0, y0)[0];
+            let y_value = y0[0][0];
+            let y_next = vec![vec![y_value + h * k]];
+
+            let dense = DenseOutput::new(t0, h, move |theta| vec![vec![y_value + theta * h * k]]);
+
+            (y_next, dense)
+        }
+    }
+
+    #[test]
+    fn interpolate_matches_the_endpoints() {
+        let mut method = LinearEuler;
+        let f = |_t: f64, y: &Vec<Vec<f64>>| vec![2. * y[0][0]];
+        let y0 = vec![vec![1.]];
+
+        let (y1, dense) = method.approximate_dense(0., 0.1, &f, &y0);
+
+        assert_eq!(dense.interpolate(0.), y0);
+        assert_eq!(dense.interpolate(1.), y1);
+    }
+
+    #[test]
+    fn evaluate_at_converts_t_to_theta() {
+        let mut method = LinearEuler;
+        let f = |_t: f64, y: &Vec<Vec<f64>>| vec![2. * y[0][0]];
+        let y0 = vec![vec![1.]];
+
+        let (_, dense) = method.approximate_dense(0., 0.2, &f, &y0);
+
+        let midpoint = dense.evaluate_at(0.1);
+        assert!((midpoint[0][0] - 1.2).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,194 @@
+//! A lazy, [Iterator]-based driver over a [Solver], replacing manual
+//! `for _ in 0..iterations { y = method.approximate(...) }` loops.
+
+use crate::dense::DenseOutputSolver;
+use crate::Solver;
+
+/// Wraps a [Solver] and steps it lazily, yielding `(t, y)` after each
+/// accepted step. Because this is a plain [Iterator], callers can use
+/// `.last()` to keep only the endpoint, `.collect()` to gather the whole
+/// trajectory, `.take_while(...)` for event-style stopping, or `.map(...)`
+/// to post-process on the fly.
+pub struct Integration<'f> {
+    method: Box<dyn Solver>,
+    f: &'f dyn Fn(f64, &Vec<Vec<f64>>) -> Vec<f64>,
+    h: f64,
+    t: f64,
+    y: Vec<Vec<f64>>,
+    stop: Box<dyn Fn(f64, &Vec<Vec<f64>>) -> bool>,
+    done: bool,
+}
+
+impl<'f> Integration<'f> {
+    /// Builds an [Integration] that starts at `(t0, y0)` and advances
+    /// `method` with a fixed step `h`, stopping as soon as `stop(t, y)`
+    /// returns `true`.
+    pub fn new(
+        method: impl Solver + 'static,
+        t0: f64,
+        y0: Vec<Vec<f64>>,
+        h: f64,
+        f: &'f dyn Fn(f64, &Vec<Vec<f64>>) -> Vec<f64>,
+        stop: impl Fn(f64, &Vec<Vec<f64>>) -> bool + 'static,
+    ) -> Self {
+        Integration {
+            method: Box::new(method),
+            f,
+            h,
+            t: t0,
+            y: y0,
+            stop: Box::new(stop),
+            done: false,
+        }
+    }
+}
+
+impl<'f> Iterator for Integration<'f> {
+    type Item = (f64, Vec<Vec<f64>>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || (self.stop)(self.t, &self.y) {
+            self.done = true;
+            return None;
+        }
+
+        self.y = self.method.approximate(self.t, self.h, self.f, &self.y);
+        self.t += self.h;
+
+        Some((self.t, self.y.clone()))
+    }
+}
+
+/// Walks an integration from `(t0, y0)` with a fixed step `h`, using
+/// `method`'s [DenseOutput][crate::dense::DenseOutput] continuous
+/// extension to emit a state at exactly each instant in `times`,
+/// regardless of the internal step pattern. `times` need not be sorted or
+/// aligned to `h`; instants before `t0` are skipped. Stepping continues
+/// for as long as instants at or after `t0` remain pending, so the
+/// largest requested instant determines how far the integration runs.
+pub fn output_at<S: DenseOutputSolver>(
+    method: &mut S,
+    t0: f64,
+    mut y: Vec<Vec<f64>>,
+    h: f64,
+    f: &dyn Fn(f64, &Vec<Vec<f64>>) -> Vec<f64>,
+    times: &[f64],
+) -> Vec<(f64, Vec<Vec<f64>>)> {
+    let mut sorted_times = times.to_vec();
+    sorted_times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let mut results = Vec::with_capacity(sorted_times.len());
+    let mut pending = sorted_times.into_iter().peekable();
+    while matches!(pending.peek(), Some(&query) if query < t0) {
+        pending.next();
+    }
+
+    let mut t = t0;
+
+    while pending.peek().is_some() {
+        let (y_next, dense) = method.approximate_dense(t, h, f, &y);
+        let t_next = t + h;
+
+        while let Some(&query) = pending.peek() {
+            if query > t_next {
+                break;
+            }
+            results.push((query, dense.evaluate_at(query)));
+            pending.next();
+        }
+
+        y = y_next;
+        t = t_next;
+    }
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct ExplicitEuler;
+
+    impl Solver for ExplicitEuler {
+        fn approximate(
+            &mut self,
+            t0: f64,
+            h: f64,
+            f: &dyn Fn(f64, &Vec<Vec<f64>>) -> Vec<f64>,
+            y0: &Vec<Vec<f64>>,
+        ) -> Vec<Vec<f64>> {
+            vec![vec![y0[0][0] + h * f(t0, y0)[0]]]
+        }
+    }
+
+    #[test]
+    fn yields_a_state_after_each_step() {
+        let f = |_t: f64, y: &Vec<Vec<f64>>| vec![-y[0][0]];
+        let integration = Integration::new(ExplicitEuler, 0., vec![vec![1.]], 0.1, &f, |_, _| false);
+
+        let trajectory: Vec<_> = integration.take(3).collect();
+
+        assert_eq!(trajectory.len(), 3);
+        assert!((trajectory[0].0 - 0.1).abs() < 1e-12);
+        assert!((trajectory[2].0 - 0.3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn stops_as_soon_as_the_predicate_fires() {
+        let f = |_t: f64, y: &Vec<Vec<f64>>| vec![-y[0][0]];
+        let integration = Integration::new(ExplicitEuler, 0., vec![vec![1.]], 0.1, &f, |t, _| t >= 0.15);
+
+        let trajectory: Vec<_> = integration.collect();
+
+        assert_eq!(trajectory.len(), 2);
+    }
+
+    struct LinearEuler;
+
+    impl Solver for LinearEuler {
+        fn approximate(
+            &mut self,
+            t0: f64,
+            h: f64,
+            f: &dyn Fn(f64, &Vec<Vec<f64>>) -> Vec<f64>,
+            y0: &Vec<Vec<f64>>,
+        ) -> Vec<Vec<f64>> {
+            self.approximate_dense(t0, h, f, y0).0
+        }
+    }
+
+    impl crate::dense::DenseOutputSolver for LinearEuler {
+        fn approximate_dense(
+            &mut self,
+            t0: f64,
+            h: f64,
+            f: &dyn Fn(f64, &Vec<Vec<f64>>) -> Vec<f64>,
+            y0: &Vec<Vec<f64>>,
+        ) -> (Vec<Vec<f64>>, crate::dense::DenseOutput) {
+            let k = f(t0, y0)[0];
+            let y_value = y0[0][0];
+            let y_next = vec![vec![y_value + h * k]];
+
+            let dense =
+                crate::dense::DenseOutput::new(t0, h, move |theta| vec![vec![y_value + theta * h * k]]);
+
+            (y_next, dense)
+        }
+    }
+
+    #[test]
+    fn output_at_samples_exactly_at_the_requested_instants() {
+        let mut method = LinearEuler;
+        let f = |_t: f64, y: &Vec<Vec<f64>>| vec![2. * y[0][0]];
+        let y0 = vec![vec![1.]];
+
+        let results = output_at(&mut method, 0., y0, 0.1, &f, &[0.05, 0.25]);
+
+        assert_eq!(results.len(), 2);
+        assert!((results[0].0 - 0.05).abs() < 1e-12);
+        assert!((results[1].0 - 0.25).abs() < 1e-12);
+        // y(0.05) interpolated within the first step: 1 + 0.5*0.1*2*1 = 1.1
+        assert!((results[0].1[0][0] - 1.1).abs() < 1e-9);
+    }
+}
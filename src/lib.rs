@@ -71,27 +71,41 @@ let error = vec![vec![(exact[0][0]-y[0][0]).abs(),(exact[0][1]-y[0][1]).abs()],v
 pub mod GMORK;
 pub mod NDMORK;
 pub mod RK;
+pub mod adaptive;
+pub mod dense;
 pub mod graph;
+pub mod integration;
+pub mod newton;
+pub mod state;
+pub mod tableau;
 
 use crate::graph::*;
+use crate::state::State;
+use num_traits::Float;
 
 const ERROR_FRACTION: f64 = 0.001;
 const MAX_ITER: u32 = 100;
 const MIN_ITER: u32 = 100;
 
 /// [Solver] is the used to indicate that a struct is a numerical scheme and can hence approximate the solution of an initial value problem.
-pub trait Solver {
+///
+/// It is generic over the scalar type `T` and the state container `S`
+/// holding the ragged per-equation, per-derivative layout, both defaulted
+/// to the crate's original `f64`/`Vec<Vec<f64>>` so that `impl Solver for
+/// MyMethod` keeps compiling unchanged. Providing other `T: Float` /
+/// `S: State<T>` combinations (e.g. a fixed-size stack-allocated `S`, or
+/// `T = f32`) cuts the per-step heap churn of the hot loop and enables
+/// higher/lower precision runs.
+pub trait Solver<T = f64, S = Vec<Vec<T>>>
+where
+    T: Float,
+    S: State<T>,
+{
     /// Given a differential equation function, initial instant, initial values, and a step size, [approximate][Solver::approximate] returns the approximation of the method/struct which implements this trait.
-    fn approximate(
-        &mut self,
-        t0: f64,
-        h: f64,
-        f: &dyn Fn(f64, &Vec<Vec<f64>>) -> Vec<f64>,
-        y0: &Vec<Vec<f64>>,
-    ) -> Vec<Vec<f64>>;
+    fn approximate(&mut self, t0: T, h: T, f: &dyn Fn(T, &S) -> Vec<T>, y0: &S) -> S;
 }
 
-/// [enum@SCC] allows to distinguish between implicit and explicit strongly connected components.
+/// [enum@SCC] allows to distinguish between implicit and explicit strongly connected components. The stage equations of an `Implicit` component can be resolved either by fixed-point iteration or, for stiff problems, by [newton::newton_solve] (see [newton::ImplicitSolve]).
 #[derive(Debug, Clone)]
 pub enum SCC {
     Implicit(Vec<usize>, Vec<usize>), // J and [|1,s|] without J
@@ -0,0 +1,108 @@
+//! A [State] abstraction generalizing the state container used by
+//! [Solver][crate::Solver] away from the hard-coded `Vec<Vec<f64>>`, so that
+//! methods can eventually run over stack-allocated, fixed-size backings or
+//! `f32`/extended-precision scalars while keeping MORK's distinctive
+//! per-entry, per-derivative indexing.
+
+use num_traits::Float;
+
+/// A state container supporting the handful of operations a [Solver][crate::Solver]
+/// needs to combine stages: an elementwise scaled accumulation (`axpy`), a
+/// uniform scaling, a same-shaped zero value, and a weighted norm against
+/// another state of the same shape (used to estimate local error, as in
+/// [adaptive][crate::adaptive]).
+pub trait State<T: Float> {
+    /// A state of the same shape as `self`, filled with zeroes.
+    fn zero_like(&self) -> Self;
+
+    /// `self += a * x`, entry by entry, matching entries up across the
+    /// ragged per-equation, per-derivative layout.
+    fn axpy(&mut self, a: T, x: &Self);
+
+    /// `self *= a`, entry by entry.
+    fn scale(&mut self, a: T);
+
+    /// The mixed absolute/relative error norm of `self` against `other`:
+    /// `sqrt(mean(((self-other)/(atol+rtol*max(|self|,|other|)))^2))`,
+    /// flattened over the ragged layout.
+    fn weighted_norm(&self, other: &Self, atol: T, rtol: T) -> T;
+}
+
+impl<T: Float> State<T> for Vec<Vec<T>> {
+    fn zero_like(&self) -> Self {
+        self.iter().map(|row| vec![T::zero(); row.len()]).collect()
+    }
+
+    fn axpy(&mut self, a: T, x: &Self) {
+        for (row, x_row) in self.iter_mut().zip(x.iter()) {
+            for (v, &xv) in row.iter_mut().zip(x_row.iter()) {
+                *v = *v + a * xv;
+            }
+        }
+    }
+
+    fn scale(&mut self, a: T) {
+        for row in self.iter_mut() {
+            for v in row.iter_mut() {
+                *v = *v * a;
+            }
+        }
+    }
+
+    fn weighted_norm(&self, other: &Self, atol: T, rtol: T) -> T {
+        let mut sum = T::zero();
+        let mut count = 0;
+
+        for (row, other_row) in self.iter().zip(other.iter()) {
+            for (&v, &ov) in row.iter().zip(other_row.iter()) {
+                let scale = atol + rtol * v.abs().max(ov.abs());
+                let scaled = (v - ov) / scale;
+                sum = sum + scaled * scaled;
+                count += 1;
+            }
+        }
+
+        (sum / T::from(count).unwrap()).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_like_preserves_the_ragged_shape() {
+        let y: Vec<Vec<f64>> = vec![vec![1., 2.], vec![3.]];
+        let zero = y.zero_like();
+        assert_eq!(zero, vec![vec![0., 0.], vec![0.]]);
+    }
+
+    #[test]
+    fn axpy_accumulates_entry_by_entry() {
+        let mut y: Vec<Vec<f64>> = vec![vec![1., 2.], vec![3.]];
+        let x: Vec<Vec<f64>> = vec![vec![1., 1.], vec![1.]];
+        y.axpy(2., &x);
+        assert_eq!(y, vec![vec![3., 4.], vec![5.]]);
+    }
+
+    #[test]
+    fn scale_multiplies_every_entry() {
+        let mut y: Vec<Vec<f64>> = vec![vec![1., 2.], vec![3.]];
+        y.scale(2.);
+        assert_eq!(y, vec![vec![2., 4.], vec![6.]]);
+    }
+
+    #[test]
+    fn weighted_norm_of_a_state_against_itself_is_zero() {
+        let y: Vec<Vec<f64>> = vec![vec![1., 2.], vec![3.]];
+        assert_eq!(y.weighted_norm(&y, 1e-6, 1e-3), 0.);
+    }
+
+    #[test]
+    fn weighted_norm_scales_with_the_difference() {
+        let y_high: Vec<Vec<f64>> = vec![vec![1.]];
+        let y_low: Vec<Vec<f64>> = vec![vec![1.1]];
+        let norm = y_high.weighted_norm(&y_low, 1e-6, 1e-3);
+        assert!((norm - (0.1_f64 / (1e-6 + 1e-3 * 1.1)).abs()).abs() < 1e-9);
+    }
+}
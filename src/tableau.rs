@@ -0,0 +1,428 @@
+//! User-constructable methods from arbitrary Butcher-tableau-style
+//! coefficients, with numerical verification of their order conditions.
+//!
+//! Alongside the stage coupling matrix `a` and the node vector `c`,
+//! [Tableau] carries one weight array per derivative level and the order
+//! of each equation in `orders`, so a single [Tableau] can combine stages
+//! for a system mixing equations of different orders (as in the crate's
+//! own multi-order example, where `y1` is second-order and `y2` is
+//! first-order). [Tableau::approximate] runs
+//! [create_computation_order][crate::create_computation_order] over the
+//! stage coupling graph and resolves each
+//! [SCC::Explicit][crate::SCC::Explicit] stage by direct substitution,
+//! reserving [newton::solve][crate::newton::solve] for the stages
+//! actually coupled inside an [SCC::Implicit][crate::SCC::Implicit]
+//! block.
+
+use crate::newton::{self, ImplicitSolve};
+use crate::{create_computation_order, Solver, SCC};
+
+/// A malformed set of tableau coefficients.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TableauError {
+    /// `a` is not square.
+    NonSquareStageMatrix { stages: usize, row_len: usize },
+    /// `c` does not have one node per stage.
+    NodeCountMismatch { stages: usize, nodes: usize },
+    /// A weight array does not have one weight per stage.
+    WeightCountMismatch { stages: usize, weights: usize },
+    /// `weights` does not have one row per derivative level needed by the
+    /// deepest equation in `orders`.
+    WeightLevelsMismatch { expected: usize, found: usize },
+    /// An equation was declared with order `0`; every equation must track
+    /// at least its own value.
+    ZeroOrderEquation { index: usize },
+}
+
+impl std::fmt::Display for TableauError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TableauError::NonSquareStageMatrix { stages, row_len } => write!(
+                f,
+                "stage matrix `a` has {stages} rows but a row of length {row_len}"
+            ),
+            TableauError::NodeCountMismatch { stages, nodes } => write!(
+                f,
+                "expected {stages} nodes in `c`, found {nodes}"
+            ),
+            TableauError::WeightCountMismatch { stages, weights } => write!(
+                f,
+                "expected {stages} weights per row, found a row of length {weights}"
+            ),
+            TableauError::WeightLevelsMismatch { expected, found } => write!(
+                f,
+                "expected {expected} weight arrays (one per derivative level of the deepest equation), found {found}"
+            ),
+            TableauError::ZeroOrderEquation { index } => write!(
+                f,
+                "equation {index} was declared with order 0; every equation must have order >= 1"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TableauError {}
+
+/// A user-defined multi-order Runge-Kutta method built from its raw
+/// coefficients rather than taken from [RK::list][crate::RK::list] or
+/// [NDMORK::list][crate::NDMORK::list].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tableau {
+    a: Vec<Vec<f64>>,
+    c: Vec<f64>,
+    weights: Vec<Vec<f64>>,
+    orders: Vec<u32>,
+    solve_mode: ImplicitSolve,
+}
+
+impl Tableau {
+    /// Builds a [Tableau] from the stage coupling matrix `a`, the node
+    /// vector `c`, one weight array per derivative level in `weights`
+    /// (level `0` combines the topmost tracked derivative, level `1` the
+    /// one below it, and so on), and the order of each equation in
+    /// `orders`. Returns a [TableauError] if the arrays are inconsistently
+    /// shaped.
+    pub fn new(
+        a: Vec<Vec<f64>>,
+        c: Vec<f64>,
+        weights: Vec<Vec<f64>>,
+        orders: Vec<u32>,
+    ) -> Result<Self, TableauError> {
+        let stages = a.len();
+
+        if let Some(row) = a.iter().find(|row| row.len() != stages) {
+            return Err(TableauError::NonSquareStageMatrix {
+                stages,
+                row_len: row.len(),
+            });
+        }
+
+        if c.len() != stages {
+            return Err(TableauError::NodeCountMismatch {
+                stages,
+                nodes: c.len(),
+            });
+        }
+
+        if let Some(row) = weights.iter().find(|row| row.len() != stages) {
+            return Err(TableauError::WeightCountMismatch {
+                stages,
+                weights: row.len(),
+            });
+        }
+
+        if let Some(index) = orders.iter().position(|&order| order == 0) {
+            return Err(TableauError::ZeroOrderEquation { index });
+        }
+
+        let max_order = *orders.iter().max().unwrap_or(&0) as usize;
+        if weights.len() != max_order {
+            return Err(TableauError::WeightLevelsMismatch {
+                expected: max_order,
+                found: weights.len(),
+            });
+        }
+
+        Ok(Tableau {
+            a,
+            c,
+            weights,
+            orders,
+            solve_mode: ImplicitSolve::FixedPoint,
+        })
+    }
+
+    /// Selects how the coupled stage equations of an implicit SCC are
+    /// resolved; defaults to the cheaper [ImplicitSolve::FixedPoint], which
+    /// is adequate away from stiffness. Stiff users can opt into
+    /// [ImplicitSolve::Newton].
+    pub fn with_solve_mode(mut self, mode: ImplicitSolve) -> Self {
+        self.solve_mode = mode;
+        self
+    }
+
+    fn stages(&self) -> usize {
+        self.c.len()
+    }
+
+    /// The order of each equation, in the order the corresponding rows
+    /// appear in `y0`.
+    pub fn orders(&self) -> &[u32] {
+        &self.orders
+    }
+
+    /// Numerically checks that this method satisfies the order conditions
+    /// up to `p`: integrates a small battery of scalar test IVPs with
+    /// known polynomial/exponential solutions at a step `h` and at `h/2`,
+    /// and confirms that the observed convergence slope
+    /// `log2(err_h / err_(h/2))` is at least `p - 0.5` for every test
+    /// problem. A problem whose error at `h/2` is already below `1e-14`
+    /// passes without a slope check; a slope above `p` also passes.
+    pub fn verify_order(&mut self, p: u32) -> bool {
+        let battery: Vec<(&dyn Fn(f64, f64) -> f64, &dyn Fn(f64) -> f64, f64)> = vec![
+            (
+                &(|_t: f64, y: f64| y) as &dyn Fn(f64, f64) -> f64,
+                &(|t: f64| t.exp()) as &dyn Fn(f64) -> f64,
+                1.,
+            ),
+            (
+                &(|_t: f64, _y: f64| 1.) as &dyn Fn(f64, f64) -> f64,
+                &(|t: f64| t) as &dyn Fn(f64) -> f64,
+                0.,
+            ),
+            (
+                &(|t: f64, _y: f64| 2. * t) as &dyn Fn(f64, f64) -> f64,
+                &(|t: f64| t * t) as &dyn Fn(f64) -> f64,
+                0.,
+            ),
+        ];
+
+        battery.into_iter().all(|(f, solution, y0)| {
+            let exact = solution(1.);
+
+            let err_h = (self.integrate_to(f, y0, 0.1) - exact).abs();
+            let err_h_half = (self.integrate_to(f, y0, 0.05) - exact).abs();
+
+            if err_h_half < 1e-14 {
+                return true;
+            }
+
+            let observed_order = (err_h / err_h_half).log2();
+            observed_order >= p as f64 - 0.5
+        })
+    }
+
+    /// Integrates the scalar, first-order IVP `y' = f(t, y)`, `y(0) = y0`,
+    /// from `t = 0` to `t = 1` with a constant step `h`.
+    fn integrate_to(&mut self, f: &dyn Fn(f64, f64) -> f64, y0: f64, h: f64) -> f64 {
+        let f_vec = |t: f64, y: &Vec<Vec<f64>>| vec![f(t, y[0][0])];
+
+        let mut y = vec![vec![y0]];
+        let steps = (1. / h).round() as u32;
+        let mut t = 0.;
+
+        for _ in 0..steps {
+            y = self.approximate(t, h, &f_vec, &y);
+            t += h;
+        }
+
+        y[0][0]
+    }
+
+    /// The Taylor prefix `sum_{p=0}^{j} h^p/p! * row[j - p]`, i.e. the
+    /// contribution to derivative level `j` that is already known from
+    /// `row` (the levels at or above `j`), with no stage slopes involved
+    /// yet.
+    fn taylor_prefix(h: f64, row: &[f64], j: usize) -> f64 {
+        let mut sum = 0.;
+        let mut h_pow = 1.;
+        let mut factorial = 1.;
+
+        for p in 0..=j {
+            sum += h_pow / factorial * row[j - p];
+            h_pow *= h;
+            factorial *= (p + 1) as f64;
+        }
+
+        sum
+    }
+
+    /// Combines derivative level `j` of one equation from its base `row`,
+    /// the per-stage coefficients `coeffs` (either a row of `a`, for an
+    /// intra-step stage value, or a row of `weights`, for the final
+    /// combination), and that equation's stage slopes `k_eq`.
+    fn combine_level(h: f64, coeffs: &[f64], k_eq: &[f64], row: &[f64], j: usize) -> f64 {
+        let prefix = Self::taylor_prefix(h, row, j);
+        let stage_sum: f64 = coeffs.iter().zip(k_eq.iter()).map(|(c, k)| c * k).sum();
+        prefix + h.powi((j + 1) as i32) * stage_sum
+    }
+
+    /// Builds the combined state passed to `f` when evaluating `stage`,
+    /// from the stage slopes of every equation known so far in `k`.
+    fn stage_state(&self, h: f64, k: &[Vec<f64>], y0: &[Vec<f64>], stage: usize) -> Vec<Vec<f64>> {
+        let a_row = &self.a[stage];
+
+        (0..self.orders.len())
+            .map(|eq| {
+                let order = self.orders[eq] as usize;
+                (0..order)
+                    .map(|j| Self::combine_level(h, a_row, &k[eq], &y0[eq], j))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Combines every equation's stage slopes into the state at `t0 + h`.
+    fn advance(&self, h: f64, k: &[Vec<f64>], y0: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        (0..self.orders.len())
+            .map(|eq| {
+                let order = self.orders[eq] as usize;
+                (0..order)
+                    .map(|j| Self::combine_level(h, &self.weights[j], &k[eq], &y0[eq], j))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl Solver for Tableau {
+    fn approximate(
+        &mut self,
+        t0: f64,
+        h: f64,
+        f: &dyn Fn(f64, &Vec<Vec<f64>>) -> Vec<f64>,
+        y0: &Vec<Vec<f64>>,
+    ) -> Vec<Vec<f64>> {
+        let s = self.stages();
+        let neq = self.orders.len();
+
+        let weight_graph: Vec<Vec<bool>> = (0..s)
+            .map(|i| (0..s).map(|j| self.a[i][j] != 0.).collect())
+            .collect();
+
+        // k[eq][stage] is the stage-`stage` slope of equation `eq`, filled
+        // in as each SCC of the computation order is resolved.
+        let mut k = vec![vec![0.; s]; neq];
+
+        for component in create_computation_order(&weight_graph) {
+            match component {
+                SCC::Explicit(stage) => {
+                    let stage_state = self.stage_state(h, &k, y0, stage);
+                    let f_values = f(t0 + self.c[stage] * h, &stage_state);
+                    for eq in 0..neq {
+                        k[eq][stage] = f_values[eq];
+                    }
+                }
+                SCC::Implicit(stages, _) => {
+                    let residual = |k_flat: &[f64]| -> Vec<f64> {
+                        let mut k_local = k.clone();
+                        for (idx, &stage) in stages.iter().enumerate() {
+                            for eq in 0..neq {
+                                k_local[eq][stage] = k_flat[idx * neq + eq];
+                            }
+                        }
+
+                        let mut residuals = Vec::with_capacity(stages.len() * neq);
+                        for &stage in stages.iter() {
+                            let stage_state = self.stage_state(h, &k_local, y0, stage);
+                            let f_values = f(t0 + self.c[stage] * h, &stage_state);
+                            for eq in 0..neq {
+                                residuals.push(k_local[eq][stage] - f_values[eq]);
+                            }
+                        }
+                        residuals
+                    };
+
+                    let solved =
+                        newton::solve(&residual, vec![0.; stages.len() * neq], self.solve_mode);
+                    for (idx, &stage) in stages.iter().enumerate() {
+                        for eq in 0..neq {
+                            k[eq][stage] = solved[idx * neq + eq];
+                        }
+                    }
+                }
+            }
+        }
+
+        self.advance(h, &k, y0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn explicit_euler() -> Tableau {
+        Tableau::new(vec![vec![0.]], vec![0.], vec![vec![1.]], vec![1]).unwrap()
+    }
+
+    #[test]
+    fn rejects_mismatched_weight_levels() {
+        let result = Tableau::new(vec![vec![0.]], vec![0.], vec![vec![1.]], vec![1, 2]);
+        assert_eq!(
+            result,
+            Err(TableauError::WeightLevelsMismatch {
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_zero_order_equations() {
+        let result = Tableau::new(vec![vec![0.]], vec![0.], vec![vec![1.]], vec![0]);
+        assert_eq!(result, Err(TableauError::ZeroOrderEquation { index: 0 }));
+    }
+
+    #[test]
+    fn rejects_mismatched_stage_matrix() {
+        let result = Tableau::new(vec![vec![0., 0.]], vec![0.], vec![vec![1.]], vec![1]);
+        assert_eq!(
+            result,
+            Err(TableauError::NonSquareStageMatrix {
+                stages: 1,
+                row_len: 2
+            })
+        );
+    }
+
+    #[test]
+    fn approximate_handles_multiple_equations() {
+        let mut method =
+            Tableau::new(vec![vec![0.]], vec![0.], vec![vec![1.]], vec![1, 1]).unwrap();
+        let f = |_t: f64, y: &Vec<Vec<f64>>| vec![y[0][0], 2. * y[1][0]];
+        let y0 = vec![vec![1.], vec![1.]];
+
+        let y1 = method.approximate(0., 0.1, &f, &y0);
+
+        assert!((y1[0][0] - 1.1).abs() < 1e-12);
+        assert!((y1[1][0] - 1.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn approximate_handles_a_second_order_equation_alongside_a_first_order_one() {
+        // eq0 (order 2): d^2y = 2, y0 = (v=0, x=0) -> exact v(h) = 2h, x(h) = h^2.
+        // eq1 (order 1): y' = y, y0 = 1 -> explicit Euler step y0 + h.
+        let weights = vec![vec![1.], vec![0.5]];
+        let mut method = Tableau::new(vec![vec![0.]], vec![0.], weights, vec![2, 1]).unwrap();
+        let f = |_t: f64, y: &Vec<Vec<f64>>| vec![2., y[1][0]];
+        let y0 = vec![vec![0., 0.], vec![1.]];
+
+        let y1 = method.approximate(0., 0.1, &f, &y0);
+
+        assert!((y1[0][0] - 0.2).abs() < 1e-12);
+        assert!((y1[0][1] - 0.01).abs() < 1e-12);
+        assert!((y1[1][0] - 1.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn approximate_resolves_explicit_stages_directly_and_implicit_stages_via_newton() {
+        // a semi-implicit 2-stage tableau: stage 0 is explicit, stage 1 is
+        // a backward-Euler-style implicit stage coupled to itself.
+        let a = vec![vec![0., 0.], vec![0., 1.]];
+        let c = vec![0., 1.];
+        let weights = vec![vec![0., 1.]];
+        let mut method = Tableau::new(a, c, weights, vec![1])
+            .unwrap()
+            .with_solve_mode(ImplicitSolve::Newton);
+        let f = |_t: f64, y: &Vec<Vec<f64>>| vec![-y[0][0]];
+        let y0 = vec![vec![1.]];
+
+        let y1 = method.approximate(0., 0.1, &f, &y0);
+
+        // Backward Euler: y1 = y0 / (1 + h).
+        assert!((y1[0][0] - 1. / 1.1).abs() < 1e-8);
+    }
+
+    #[test]
+    fn verify_order_accepts_first_order_euler() {
+        let mut method = explicit_euler();
+        assert!(method.verify_order(1));
+    }
+
+    #[test]
+    fn with_solve_mode_defaults_to_fixed_point() {
+        let method = explicit_euler();
+        assert_eq!(method.solve_mode, ImplicitSolve::FixedPoint);
+    }
+}